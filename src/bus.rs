@@ -0,0 +1,28 @@
+//! 共享 SPI1 总线。
+//!
+//! 显示屏与触摸控制器挂在同一条物理 SPI1 总线上，但工作参数不同：
+//! ST7789 跑在 50 MHz / Mode 3，而 XPT2046 只能跑在 ~200 kHz 且需要 Mode 0。
+//! 这里用一个 `Mutex` 包裹的总线 + 每个设备各自的 `SpiDeviceWithConfig`，
+//! 在每次收发前自动把总线切换到对应设备的频率/模式，并各自驱动独立的片选。
+//!
+//! 两个消费者都不需要真正跨 `.await` 持有总线：显示屏一直是同步驱动的
+//! （`ST7789` 走阻塞的 `embedded_hal::spi::SpiDevice`），触摸控制器虽然
+//! 自身 `read` 是 `async fn`（中间靠 `Timer` 等待 ADC 稳定），但每次 SPI
+//! 收发本身也就是几个字节的阻塞传输。所以这里统一用 `blocking` 版本的
+//! 共享总线：单个 `NoopRawMutex` + `RefCell` 就够用，不必为此拉一个只有
+//! 显示屏用不上的 async `Mutex`。
+
+use core::cell::RefCell;
+
+use embassy_embedded_hal::shared_bus::blocking::spi::SpiDeviceWithConfig;
+use embassy_stm32::gpio::Output;
+use embassy_stm32::mode::Async;
+use embassy_stm32::spi::Spi;
+use embassy_sync::blocking_mutex::raw::NoopRawMutex;
+use embassy_sync::blocking_mutex::Mutex;
+
+/// SPI1 物理总线，由显示屏与触摸控制器共享。
+pub type Spi1Bus = Mutex<NoopRawMutex, RefCell<Spi<'static, Async>>>;
+
+/// 挂在共享总线上的某个逻辑设备（自带独立 CS 与总线参数）。
+pub type SharedSpiDevice<'a> = SpiDeviceWithConfig<'a, NoopRawMutex, Spi<'static, Async>, Output<'static>>;
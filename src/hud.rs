@@ -0,0 +1,69 @@
+//! 文本/数字 HUD 叠加层。
+//!
+//! 提供类似常见 `LCD_ShowNum`/`LCD_ShowString` 例程的小工具，省去每次都要
+//! 手搓 `Text`/`MonoTextStyle` 的麻烦，用来在图片上叠加实时读数（计时、
+//! 编码器计数、由计数差分出来的"速度"等），让板子也能当秒表/里程表用。
+
+use core::fmt::Write;
+
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::mono_font::{ascii::FONT_6X13, MonoTextStyle};
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::{Baseline, Text};
+use embedded_hal::digital::OutputPin;
+use heapless::String;
+use st7789::ST7789;
+
+use crate::partial::fill_region;
+use crate::ui::DirtyList;
+
+/// 数字缓冲区的最大长度，够容纳一个 `i32` 加符号。
+const NUMBER_BUF_LEN: usize = 12;
+/// 清背景用的像素缓冲区容量：按最长数字串 * FONT_6X13 字形尺寸估算。
+const CLEAR_BUF_LEN: usize = NUMBER_BUF_LEN * 6 * 13;
+
+/// 在指定位置画一行字符串。
+pub fn draw_text(display: &mut impl DrawTarget<Color = Rgb565>, position: Point, text: &str, color: Rgb565) {
+    let style = MonoTextStyle::new(&FONT_6X13, color);
+    let _ = Text::new(text, position, style).draw(display);
+}
+
+/// 和上面画字符串一样，是右对齐、零填充到 `width` 位的数字，但只局部刷新：
+/// 先用 [`fill_region`] 把这个数字占用的矩形刷成背景色（避免新数字比
+/// 旧数字窄时残留旧字形），再把新文字画上去，并记录到 `dirty` 里。
+/// `position` 是这个数字矩形的左上角。
+/// 只有能拿到具体 `ST7789<DI, RST, BL>` 类型（而非泛型 `DrawTarget`）时才能
+/// 这样做，因为局部刷新要用到驱动自己的地址窗口接口。
+pub fn draw_number_partial<DI, RST, BL>(
+    display: &mut ST7789<DI, RST, BL>,
+    position: Point,
+    value: i32,
+    width: usize,
+    fg: Rgb565,
+    bg: Rgb565,
+    dirty: &mut DirtyList,
+) where
+    DI: display_interface::WriteOnlyDataCommand,
+    RST: OutputPin,
+    BL: OutputPin,
+{
+    let mut buf: String<NUMBER_BUF_LEN> = String::new();
+    let _ = write!(buf, "{value:0width$}");
+
+    let glyph = FONT_6X13.character_size;
+    let region = Rectangle::new(
+        position,
+        Size::new(glyph.width * buf.len() as u32, glyph.height),
+    );
+
+    let clear_pixels = [bg; CLEAR_BUF_LEN];
+    let pixel_count = (region.size.width * region.size.height) as usize;
+    fill_region(display, region, &clear_pixels[..pixel_count]);
+
+    // 按顶端对齐画字，字形正好落在上面清掉的矩形里。
+    let style = MonoTextStyle::new(&FONT_6X13, fg);
+    let _ = Text::with_baseline(&buf, position, style, Baseline::Top).draw(display);
+    dirty.mark(region);
+}
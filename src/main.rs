@@ -1,16 +1,38 @@
 #![no_std]
 #![no_main]
 
+// Slint 软件渲染 UI 后端是可选的（见 `src/slint_ui.rs` 顶部文档），需要一个
+// 全局分配器，因此只在启用 `slint-ui` feature 时才拉入 `alloc`。
+#[cfg(feature = "slint-ui")]
+extern crate alloc;
+
+mod bus;
+mod hud;
+mod partial;
+#[cfg(feature = "slint-ui")]
+mod slint_ui;
+mod storage;
+mod tasks;
+mod touch;
+mod ui;
+mod widgets;
+
+use core::cell::RefCell;
+
 use defmt::{error, info};
+use embassy_embedded_hal::shared_bus::blocking::spi::SpiDeviceWithConfig;
 use embassy_executor::Spawner;
-use embassy_stm32::gpio::Input;
+use embassy_futures::select::{select, Either};
+use embassy_stm32::exti::ExtiInput;
 use embassy_stm32::spi::{Config as SpiConfig, Spi};
 use embassy_stm32::timer::qei::{Qei, QeiPin};
 use embassy_stm32::{
     gpio::{Level, Output, Pull, Speed},
     time::Hertz,
 };
+use embassy_sync::blocking_mutex::Mutex;
 use embassy_time::{Delay, Timer};
+use static_cell::StaticCell;
 use {defmt_rtt as _, panic_probe as _};
 
 // SPI 显示接口
@@ -27,6 +49,22 @@ use embedded_graphics::{
     text::Text,
 };
 
+use touch::Touch;
+use ui::Input as UiInput;
+use ui::{HomeScreen, Screen, ScreenStack, Screens};
+
+#[cfg(feature = "slint-ui")]
+use embedded_alloc::Heap;
+
+/// SPI1 共享总线的静态存储（显示屏与触摸控制器共用）。
+static SPI1_BUS: StaticCell<bus::Spi1Bus> = StaticCell::new();
+
+/// `slint-ui` feature 用到的堆：Slint 的 `Platform`/`WindowAdapter` 依赖
+/// `alloc::rc::Rc`，no_std 下需要显式提供一个全局分配器。
+#[cfg(feature = "slint-ui")]
+#[global_allocator]
+static HEAP: Heap = Heap::empty();
+
 // 屏幕尺寸常量（240x240）
 const SCREEN_WIDTH: i32 = 240;
 const SCREEN_HEIGHT: i32 = 240;
@@ -38,7 +76,7 @@ const KEY_SCAN_INTERVAL: u64 = 50;
 const TOTAL_IMAGES: u8 = 5;
 
 #[embassy_executor::main]
-async fn main(_spawner: Spawner) -> ! {
+async fn main(spawner: Spawner) -> ! {
     let mut peripheral_config = embassy_stm32::Config::default();
     {
         use embassy_stm32::rcc::*;
@@ -67,26 +105,57 @@ async fn main(_spawner: Spawner) -> ! {
 
     info!("hello rust");
 
-    // 配置SPI
-    let mut spi_config = SpiConfig::default();
-    spi_config.frequency = Hertz(50_000_000);
+    // `slint-ui` feature: 给 Slint 的 Rc/Box 初始化一块静态堆。
+    #[cfg(feature = "slint-ui")]
+    {
+        use core::mem::MaybeUninit;
+        const HEAP_SIZE: usize = 64 * 1024;
+        static mut HEAP_MEM: [MaybeUninit<u8>; HEAP_SIZE] = [MaybeUninit::uninit(); HEAP_SIZE];
+        unsafe { HEAP.init(HEAP_MEM.as_ptr() as usize, HEAP_SIZE) }
+    }
 
-    // 设置 SPI 模式为 Mode 3
-    spi_config.mode = embassy_stm32::spi::Mode {
+    // 配置SPI1总线（裸总线参数，实际频率/模式由各设备的 SpiDeviceWithConfig 接管）。
+    let mut bus_config = SpiConfig::default();
+    bus_config.frequency = Hertz(50_000_000);
+    bus_config.mode = embassy_stm32::spi::Mode {
         polarity: embassy_stm32::spi::Polarity::IdleHigh,
         phase: embassy_stm32::spi::Phase::CaptureOnSecondTransition,
     };
 
-    let spi = Spi::new_txonly(p.SPI1, p.PA5, p.PA7, p.DMA2_CH2, spi_config);
+    // 显示屏与触摸控制器共享同一条全双工 SPI1 总线，各自独立片选。
+    let spi1 = Spi::new(
+        p.SPI1, p.PA5, p.PA7, p.PB4, p.DMA2_CH2, p.DMA2_CH1, bus_config,
+    );
+    let spi1_bus = SPI1_BUS.init(Mutex::new(RefCell::new(spi1)));
+
+    // 显示屏：50 MHz / Mode 3。
+    let mut display_spi_config = SpiConfig::default();
+    display_spi_config.frequency = Hertz(50_000_000);
+    display_spi_config.mode = embassy_stm32::spi::Mode {
+        polarity: embassy_stm32::spi::Polarity::IdleHigh,
+        phase: embassy_stm32::spi::Phase::CaptureOnSecondTransition,
+    };
+    let display_cs = Output::new(p.PA4, Level::High, Speed::VeryHigh);
+    let display_spi = SpiDeviceWithConfig::new(spi1_bus, display_cs, display_spi_config);
+
+    // 触摸控制器（XPT2046）：~200 kHz / Mode 0。
+    let mut touch_spi_config = SpiConfig::default();
+    touch_spi_config.frequency = Hertz(200_000);
+    touch_spi_config.mode = embassy_stm32::spi::Mode {
+        polarity: embassy_stm32::spi::Polarity::IdleLow,
+        phase: embassy_stm32::spi::Phase::CaptureOnFirstTransition,
+    };
+    let touch_cs = Output::new(p.PB5, Level::High, Speed::VeryHigh);
+    let touch_spi = SpiDeviceWithConfig::new(spi1_bus, touch_cs, touch_spi_config);
+    let mut touch = Touch::new(touch_spi);
 
     // 控制引脚
-    let cs = Output::new(p.PA4, Level::High, Speed::VeryHigh);
     let dc = Output::new(p.PA2, Level::Low, Speed::VeryHigh);
     let rst = Output::new(p.PA6, Level::High, Speed::VeryHigh);
     let bl = Output::new(p.PA1, Level::Low, Speed::VeryHigh);
 
     // 创建显示接口
-    let di = SPIInterface::new(spi, dc, cs);
+    let di = SPIInterface::new(display_spi, dc);
 
     // 创建ST7789驱动实例
     let mut display = ST7789::new(
@@ -124,106 +193,283 @@ async fn main(_spawner: Spawner) -> ! {
         info!("背光已打开");
     }
 
-    // 按键初始化
-    let key1 = Input::new(p.PF12, Pull::Up);
-    let key2 = Input::new(p.PF13, Pull::Up);
-    let key3 = Input::new(p.PF14, Pull::Up);
-    let key4 = Input::new(p.PF15, Pull::Up);
-
-    let mut current_image = 0; // 当前显示的图像索引
-
-    // 初始显示默认图像
-    show_image(&mut display, current_image).await;
+    // 按键初始化：EXTI 驱动，交给 `tasks::button_task` 异步等下降沿做防抖，
+    // 主循环不再直接轮询电平。
+    let key1 = ExtiInput::new(p.PF12, p.EXTI12, Pull::Up);
+    let key2 = ExtiInput::new(p.PF13, p.EXTI13, Pull::Up);
+    let key3 = ExtiInput::new(p.PF14, p.EXTI14, Pull::Up);
+    let key4 = ExtiInput::new(p.PF15, p.EXTI15, Pull::Up);
 
     // 配置旋转编码器引脚
     let encoder_ch1_pin = QeiPin::new_ch1(p.PE9);
     let encoder_ch2_pin = QeiPin::new_ch2(p.PE11);
 
     // 创建QEI实例
-    let mut qei = Qei::new(p.TIM1, encoder_ch1_pin, encoder_ch2_pin);
+    let qei = Qei::new(p.TIM1, encoder_ch1_pin, encoder_ch2_pin);
 
-    // 获取初始位置
-    let mut last_position = qei.count() as i32;
-    info!("初始位置: {}", last_position);
+    // 按键/编码器任务各自独立运行，通过 `tasks::EVENTS` 通道发送导航事件；
+    // 两条 UI 路径（embedded-graphics 直接 blit 与可选的 Slint 后端）都
+    // 消费同一个事件通道，按键消抖/编码器合档的逻辑只有这一份，不会被
+    // 按键长按或编码器轮询卡住。
+    spawner.spawn(tasks::button_task(key1, UiInput::Select)).unwrap();
+    spawner.spawn(tasks::button_task(key2, UiInput::Back)).unwrap();
+    spawner.spawn(tasks::button_task(key3, UiInput::Next)).unwrap();
+    spawner.spawn(tasks::button_task(key4, UiInput::Previous)).unwrap();
+    spawner.spawn(tasks::encoder_task(qei)).unwrap();
+
+    // `slint-ui` feature：改用 Slint 的软件渲染器驱动显示屏，彻底取代下面
+    // 这条基于 embedded-graphics 直接 blit 的 UI 路径；按键/编码器仍然走
+    // 上面刚 spawn 的中断驱动任务，见 `slint_ui::run` 内部对
+    // `tasks::EVENTS` 的消费。
+    #[cfg(feature = "slint-ui")]
+    return slint_ui::run(display, touch).await;
+
+    // 恢复上次保存的图片/背光状态（掉电持久化，存在专用 Flash 扇区）。
+    let mut storage = storage::Storage::init(p.FLASH);
+    let saved_image = storage.load(storage::Tag::CurrentImage);
+    let mut current_image = saved_image.unwrap_or(1);
+    let mut backlight_on = storage.load(storage::Tag::BacklightOn).map(|v| v != 0).unwrap_or(true);
+    if let Err(_) = display.set_backlight(
+        if backlight_on {
+            BacklightState::On
+        } else {
+            BacklightState::Off
+        },
+        &mut delay,
+    ) {
+        error!("恢复背光状态失败");
+    }
+
+    // 屏幕导航栈：存储里有上次保存的图片时，启动时直接进入图库并恢复到
+    // 那张图片（不区分掉电前停在哪个屏幕）；主屏幕
+    // 也带入上次保存的背光状态和图库位置，保证首次进入设置页/图库时显示
+    // 的状态跟之前一致。
+    let mut screens = ScreenStack::new(Screens::Home(HomeScreen::new(backlight_on, current_image)));
+    if let Some(image) = saved_image {
+        screens.push(Screens::ImageGallery(ui::ImageGalleryScreen::with_image(image)));
+    }
 
-    // 主循环 - 按键扫描与图像显示
+    // 初始绘制当前屏幕。
+    screens.draw(&mut display);
+
+    // 编码器位置：不再直接读 QEI 寄存器（已经被 `encoder_task` 持有），
+    // 改成累计收到的 Next/Previous 事件数，HUD 用它展示"档位"与"速度"。
+    let mut position: i32 = 0;
+
+    // HUD 叠加层状态：经过的秒数、编码器位置与上一次间隔的"速度"。
+    let mut ticks: u32 = 0;
+    let mut last_hud_count: i32 = i32::MAX;
+    let mut last_hud_seconds: u32 = u32::MAX;
+    let mut last_hud_speed: i32 = i32::MAX;
+    let mut last_tick_count: i32 = position;
+    const TICKS_PER_SECOND: u32 = 1000 / KEY_SCAN_INTERVAL as u32;
+    // 本帧累积的脏矩形，用于局部刷新 HUD 数字。
+    let mut dirty = ui::DirtyList::new();
+
+    // 图库翻页防抖：编码器连续转动时只记下"待落盘"的最新图片和对应的
+    // tick，真正写入 Flash 要等安静够一小段时间再做，否则一次长按/快速
+    // 翻页会在按追加日志模型设计的存储扇区里炸出一堆记录，提前顶满触发
+    // `Storage::compact`。
+    const IMAGE_SAVE_IDLE_TICKS: u32 = TICKS_PER_SECOND / 2;
+    let mut pending_image: Option<(u8, u32)> = None;
+
+    // 触摸沿检测：`touch.read()` 只是按压力判断"这一刻有没有压着"，手指
+    // 按住不抬起的情况下每个 tick 都会重新采样到同一次触摸，不像按键任务
+    // 那样天然只在下降沿触发一次。这里记下上一帧的按压状态，只在
+    // 未按下→按下这个沿上翻译成事件，按住不放的后续 tick 不再重复生成。
+    let mut touch_down = false;
+
+    // 主循环 - 只消费 `tasks::EVENTS`/触摸事件并渲染，按键防抖和编码器合档
+    // 都已经交给 `tasks` 里的异步任务处理。
+    //
+    // `touch.read()` 是一次性采样（先两次短 SPI 传输判断压力，压着时再
+    // 各采几次 X/Y 取中位数），不是一个
+    // 会一直等到真正触摸才完成的 future，所以不能把它拿去跟 `Timer` 一起
+    // `select`：那样每次循环几乎立刻从触摸分支返回，主循环会退化成按 SPI
+    // 采样速度忙等，而不是维持 ~20 Hz 节奏，也会让下面按每次循环计数的
+    // `ticks`/`speed` 跑飞。改成每个 tick 的 `Timer` 分支里顺带采一次触摸。
     loop {
-        // 检测按键1
-        if key1.get_level() == Level::Low {
-            Timer::after_millis(DEBOUNCE_DELAY).await;
-            if key1.get_level() == Level::Low {
-                info!("按键1按下 - 显示图片1");
-                current_image = 1;
-                show_image(&mut display, current_image).await;
-                // 等待按键释放
-                while key1.get_level() == Level::Low {
-                    Timer::after_millis(KEY_SCAN_INTERVAL).await;
+        // 清空上一帧的脏矩形；本帧不管是 `draw_partial`（按钮高亮、图库
+        // 翻页）还是下面 HUD 数字的局部刷新，标记的区域都记到同一份
+        // `dirty` 里，方便末尾统一打日志。
+        dirty.clear();
+
+        // `tasks::EVENTS` 是个有 8 条余量的 channel：编码器快速转动或者连按
+        // 几个按键时，可能已经有好几条事件排在队里，`select` 会连续从
+        // `Either::First` 立刻返回，完全不经过下面的 `Timer` 分支。`ticks`
+        // 是墙钟时间的唯一来源，只能在 `Timer` 真正到点（`Either::Second`）
+        // 时才累加一次，不然事件扎堆的那几帧会把 `ticks`（进而 HUD 的
+        // "秒数"）跑到比实际经过的时间更快，而且永远不会追回来。
+        let event = match select(tasks::EVENTS.receive(), Timer::after_millis(KEY_SCAN_INTERVAL))
+            .await
+        {
+            // 按键/编码器任务发来的导航事件。
+            Either::First(event) => Some(event),
+            // 每个 tick 到点时顺带采样一次触摸：只让当前屏幕按自己画的
+            // 按钮热区翻译成具体事件（比如点中主屏幕上的某个按钮）；没有
+            // 画按钮的屏幕（`hit_test` 默认实现返回 `None`）不识别任何
+            // 触摸坐标，不能瞎猜成一次确认事件——不然停在设置页上随便
+            // 点一下屏幕的任何位置都会被当成 `Select` 切换背光。
+            Either::Second(()) => {
+                ticks += 1;
+                match touch.read().await {
+                    Some(point) => {
+                        // 只在按下沿（上一帧没按、这一帧按了）翻译成事件；
+                        // 手指按住不放时后续每个 tick 都会重新采样到同一
+                        // 次触摸，不加这层沿检测会像连续点击一样反复触发。
+                        let just_pressed = !touch_down;
+                        touch_down = true;
+                        if just_pressed {
+                            info!("触摸坐标: ({}, {})", point.x, point.y);
+                            screens.current().hit_test(point)
+                        } else {
+                            None
+                        }
+                    }
+                    None => {
+                        touch_down = false;
+                        None
+                    }
                 }
             }
-        }
-        // 检测按键2
-        else if key2.get_level() == Level::Low {
-            Timer::after_millis(DEBOUNCE_DELAY).await;
-            if key2.get_level() == Level::Low {
-                info!("按键2按下 - 显示图片2");
-                current_image = 2;
-                show_image(&mut display, current_image).await;
-                while key2.get_level() == Level::Low {
-                    Timer::after_millis(KEY_SCAN_INTERVAL).await;
-                }
+        };
+
+        if let Some(event) = event {
+            if event == UiInput::Next {
+                position += 1;
+            } else if event == UiInput::Previous {
+                position -= 1;
             }
-        }
-        // 检测按键3
-        else if key3.get_level() == Level::Low {
-            Timer::after_millis(DEBOUNCE_DELAY).await;
-            if key3.get_level() == Level::Low {
-                info!("按键3按下 - 显示图片3");
-                current_image = 3;
-                show_image(&mut display, current_image).await;
-                while key3.get_level() == Level::Low {
-                    Timer::after_millis(KEY_SCAN_INTERVAL).await;
-                }
+
+            // 换屏（Push/Pop）没有"上次画的状态"可以比对，退回整屏绘制；
+            // 停留在同一块屏幕上（比如 Home 切换按钮高亮、图库翻页）交给
+            // `draw_partial`，它会按屏幕自己的判断局部刷新或者照样整屏画。
+            let screen_changed = screens.dispatch(event);
+            let full_repaint = if screen_changed {
+                screens.draw(&mut display);
+                true
+            } else {
+                screens.draw_partial(&mut display, &mut dirty)
+            };
+
+            // 整屏重绘会把 HUD 数字所在的区域也一起覆盖掉（HUD 叠加层画在
+            // 当前屏幕内容之上），而下面几个 `last_hud_*` 只在数值变化时
+            // 才重绘对应数字。如果不在这里把它们复位，数值恰好没变的这一
+            // 帧就不会把被覆盖的数字补回来，HUD 要等到下次数值变化（最长
+            // 等 `count`/`speed` 不动就遥遥无期）才会重新出现。
+            if full_repaint {
+                last_hud_seconds = u32::MAX;
+                last_hud_count = i32::MAX;
+                last_hud_speed = i32::MAX;
             }
-        }
-        // 检测按键4
-        else if key4.get_level() == Level::Low {
-            Timer::after_millis(DEBOUNCE_DELAY).await;
-            if key4.get_level() == Level::Low {
-                info!("按键4按下 - 显示图片4");
-                current_image = 4;
-                show_image(&mut display, current_image).await;
-                while key4.get_level() == Level::Low {
-                    Timer::after_millis(KEY_SCAN_INTERVAL).await;
+
+            // 把会影响下次开机状态的改动写回持久化存储（背光开关改动少，
+            // 直接写；图库翻页改动频繁，只记下"待落盘"状态，真正的 Flash
+            // 写入交给下面的防抖逻辑，或者离开图库时立即补写）。
+            match screens.current() {
+                Screens::ImageGallery(gallery) => {
+                    current_image = gallery.current_image();
+                    pending_image = Some((current_image, ticks));
+                }
+                Screens::Settings(settings) => {
+                    // 只有背光开关真的变了（`Select` 切换）才落盘/驱动硬件；
+                    // `Next`/`Previous` 在设置页是 `Transition::Stay` 的
+                    // 空操作（见 `ui::SettingsScreen::handle`），停在这个
+                    // 屏幕上转编码器会让这个分支每个 tick 都跑一遍，不加
+                    // 这层判断就会跟图库翻页防抖之前的问题一样，往按追加
+                    // 日志模型设计的存储扇区里灌满记录，提前触发
+                    // `Storage::compact`。
+                    let new_backlight_on = settings.backlight_on();
+                    if new_backlight_on != backlight_on {
+                        backlight_on = new_backlight_on;
+                        storage.store(storage::Tag::BacklightOn, backlight_on as u8);
+                        let _ = display.set_backlight(
+                            if backlight_on {
+                                BacklightState::On
+                            } else {
+                                BacklightState::Off
+                            },
+                            &mut delay,
+                        );
+                    }
+                }
+                // 从设置页/图库按返回回到主屏幕：把最新的背光状态和图库
+                // 位置都带回去，这样下次从主屏幕再次进入对应子屏幕时显示
+                // 的状态还是对的；图库那边如果还有没落盘的翻页防抖在排队，
+                // 离开的这一刻就立即补写，不用等到下次空闲。
+                Screens::Home(home) => {
+                    home.set_backlight(backlight_on);
+                    home.set_current_image(current_image);
+                    if let Some((image, _)) = pending_image.take() {
+                        storage.store(storage::Tag::CurrentImage, image);
+                    }
                 }
             }
         }
 
-        // 旋转编码器处理
-        let current_position = qei.count() as i32;
-        let position_diff = current_position - last_position;
-
-        // 检测到有效旋转（差值大于1或小于-1，防止抖动）
-        if position_diff > 1 {
-            info!("向右旋转: {}", position_diff);
-            // 显示下一张图片，循环处理
-            current_image = (current_image + 1) % TOTAL_IMAGES;
-            show_image(&mut display, current_image).await;
-            last_position = current_position;
-        } else if position_diff < -1 {
-            info!("向左旋转: {}", position_diff);
-            // 显示上一张图片，循环处理
-            current_image = (current_image + TOTAL_IMAGES - 1) % TOTAL_IMAGES;
-            show_image(&mut display, current_image).await;
-            last_position = current_position;
+        // 图库翻页防抖：安静够 `IMAGE_SAVE_IDLE_TICKS` 个 tick（约 500ms）
+        // 没有新的翻页事件，才把最后一次的图片落盘，而不是每次 Next/
+        // Previous 都写一次 Flash。
+        if let Some((image, changed_tick)) = pending_image {
+            if ticks.wrapping_sub(changed_tick) >= IMAGE_SAVE_IDLE_TICKS {
+                storage.store(storage::Tag::CurrentImage, image);
+                pending_image = None;
+            }
         }
 
-        // 短暂延迟，降低CPU占用
-        Timer::after_millis(KEY_SCAN_INTERVAL).await;
+        // HUD 叠加层：运行秒数、编码器计数、及两次轮询间的计数差（"速度"）。
+        // 只有数值变化时才重绘，且每次只局部刷新这个数字的矩形区域
+        // （见 `hud::draw_number_partial` / `partial::fill_region`），
+        // 而不是像 `show_image_sync` 那样推送整屏像素。`ticks` 本身已经在
+        // 上面的 `Timer` 分支里按墙钟时间累加过了，这里只读不加。
+        let seconds = ticks / TICKS_PER_SECOND;
+        let count = position;
+        let speed = count - last_tick_count;
+        last_tick_count = count;
+        if seconds != last_hud_seconds {
+            hud::draw_number_partial(
+                &mut display,
+                Point::new(150, 4),
+                seconds as i32,
+                5,
+                Rgb565::YELLOW,
+                Rgb565::BLACK,
+                &mut dirty,
+            );
+            last_hud_seconds = seconds;
+        }
+        if count != last_hud_count {
+            hud::draw_number_partial(
+                &mut display,
+                Point::new(150, 20),
+                count,
+                6,
+                Rgb565::YELLOW,
+                Rgb565::BLACK,
+                &mut dirty,
+            );
+            last_hud_count = count;
+        }
+        if speed != last_hud_speed {
+            hud::draw_number_partial(
+                &mut display,
+                Point::new(150, 36),
+                speed,
+                4,
+                Rgb565::YELLOW,
+                Rgb565::BLACK,
+                &mut dirty,
+            );
+            last_hud_speed = speed;
+        }
+        if dirty.regions().next().is_some() {
+            info!("本帧局部刷新了 {} 块脏矩形", dirty.regions().count());
+        }
     }
 }
 
-/// 图像显示函数
-async fn show_image(
+/// 按图像索引绘制到显示设备上（同步，供各屏幕在 `draw` 中调用）。
+pub(crate) fn show_image_sync(
     display: &mut impl embedded_graphics::draw_target::DrawTarget<Color = Rgb565>,
     image_idx: u8,
 ) {
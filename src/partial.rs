@@ -0,0 +1,27 @@
+//! 局部刷新：把显示窗口通过 CASET(0x2A)/RASET(0x2B) 收窄到变化区域的
+//! 包围盒，而不是像 `show_image_sync` 那样每次都推送整屏 240x240 像素。
+//! 对只改了一个按钮或一行数字的交互式界面，这能把单帧经过 SPI 的数据量
+//! 从几十 KB 降到几百字节。
+
+use embedded_graphics::pixelcolor::raw::RawU16;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_hal::digital::OutputPin;
+use st7789::ST7789;
+
+/// 把 `region` 范围内的像素写入显示屏，只设置该区域对应的地址窗口
+/// （ST7789 驱动内部据此发出 CASET/RASET + RAMWR），不触碰屏幕其它部分。
+pub fn fill_region<DI, RST, BL>(display: &mut ST7789<DI, RST, BL>, region: Rectangle, pixels: &[Rgb565])
+where
+    DI: display_interface::WriteOnlyDataCommand,
+    RST: OutputPin,
+    BL: OutputPin,
+{
+    let sx = region.top_left.x as u16;
+    let sy = region.top_left.y as u16;
+    let ex = sx + region.size.width as u16 - 1;
+    let ey = sy + region.size.height as u16 - 1;
+    let colors = pixels.iter().map(|color| RawU16::from(*color).into_inner());
+    let _ = display.set_pixels(sx, sy, ex, ey, colors);
+}
@@ -0,0 +1,260 @@
+//! 可选的 Slint 软件渲染 UI 后端（`slint-ui` feature）。
+//!
+//! 默认情况下应用用 `embedded-graphics` 直接往 `ST7789` 上画位图/控件
+//! （见 [`crate::ui`]、[`crate::widgets`]）。这个模块提供另一条路：用
+//! Slint 的软件渲染器渲染一个声明式的 `.slint` 菜单/画廊界面，通过
+//! [`St7789LineBuffer`] 把每次渲染出的扫描线范围刷到显示屏——只保留一条
+//! 扫描线的缓冲区，而不是整屏 framebuffer，内存占用随屏幕宽度线性增长。
+//!
+//! `Cargo.toml` 里对应的 `slint`/`embedded-alloc` 依赖和 `slint-ui` feature
+//! 开关见仓库根目录的 manifest。
+//! Slint 的 `Platform`/`WindowAdapter` 用到 `alloc::rc::Rc`，no_std 下需要一个全局分配器。
+
+use core::ops::Range;
+
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use embassy_futures::select::{select, Either};
+use embassy_time::{Instant, Timer};
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_hal::digital::OutputPin;
+use slint::platform::software_renderer::{LineBufferProvider, MinimalSoftwareWindow, RepaintBufferType};
+use slint::platform::{Platform, WindowEvent};
+use slint::{LogicalPosition, PhysicalSize, PlatformError};
+use st7789::ST7789;
+
+use crate::partial::fill_region;
+use crate::tasks;
+use crate::touch::Touch;
+use crate::ui::Input as UiInput;
+use crate::{KEY_SCAN_INTERVAL, SCREEN_HEIGHT, SCREEN_WIDTH};
+
+slint::slint! {
+    // 浮雕按钮的 Slint 版本：`embedded-graphics` 那边有 `widgets::draw_button`，
+    // 这里对应的菜单项用选中态切换背景/边框颜色，点击时转发给父级。
+    component MenuButton inherits Rectangle {
+        in property <string> label;
+        in property <bool> selected;
+        callback clicked();
+
+        height: 36px;
+        border-radius: 4px;
+        background: selected ? #1e3c1e : #162816;
+        border-width: 1px;
+        border-color: selected ? #ffcc00 : #335533;
+
+        Text {
+            text: label;
+            color: white;
+            horizontal-alignment: center;
+            vertical-alignment: center;
+        }
+
+        TouchArea {
+            clicked => { root.clicked(); }
+        }
+    }
+
+    // 声明式的菜单/画廊界面：`current-view` 在两个视图间切换，菜单项的
+    // 选中态由 `selected-index` 驱动，跟 `ui::HomeScreen` 里的 `selected`
+    // 字段是同一个角色，只是这边交给 Slint 的属性系统管。
+    export component AppWindow inherits Window {
+        width: 240px;
+        height: 240px;
+        background: black;
+
+        in-out property <int> selected-index: 0;
+        in-out property <string> current-view: "menu";
+
+        callback select-next();
+        callback select-previous();
+        callback activate();
+        callback back();
+
+        select-next => { selected-index = 1 - selected-index; }
+        select-previous => { selected-index = 1 - selected-index; }
+        activate => {
+            if (selected-index == 0) {
+                current-view = "gallery";
+            }
+        }
+        back => { current-view = "menu"; }
+
+        if current-view == "menu": VerticalLayout {
+            padding: 12px;
+            spacing: 8px;
+
+            Text {
+                text: "主菜单";
+                color: white;
+                font-size: 14px;
+            }
+
+            MenuButton {
+                label: "图片画廊";
+                selected: selected-index == 0;
+                clicked => {
+                    selected-index = 0;
+                    root.activate();
+                }
+            }
+
+            MenuButton {
+                label: "设置";
+                selected: selected-index == 1;
+                clicked => { selected-index = 1; }
+            }
+        }
+
+        if current-view == "gallery": Rectangle {
+            background: #203040;
+
+            Text {
+                text: "图片画廊（占位）\n按返回键回到菜单";
+                color: white;
+                horizontal-alignment: center;
+                vertical-alignment: center;
+            }
+
+            TouchArea {
+                clicked => { root.back(); }
+            }
+        }
+    }
+}
+
+/// 把 Slint 的计时器接到 embassy 的单调时钟上。
+struct EmbassyPlatform {
+    window: Rc<MinimalSoftwareWindow>,
+    start: Instant,
+}
+
+impl Platform for EmbassyPlatform {
+    fn create_window_adapter(
+        &self,
+    ) -> Result<Rc<dyn slint::platform::WindowAdapter>, PlatformError> {
+        Ok(self.window.clone())
+    }
+
+    fn duration_since_start(&self) -> core::time::Duration {
+        core::time::Duration::from_micros(self.start.elapsed().as_micros())
+    }
+}
+
+/// 单条扫描线的行缓冲区：每渲染完一行就立刻经 [`fill_region`] 刷到显示屏，
+/// 不持有整屏 framebuffer。
+struct St7789LineBuffer<'a, DI, RST, BL> {
+    display: &'a mut ST7789<DI, RST, BL>,
+    buffer: [Rgb565; SCREEN_WIDTH as usize],
+}
+
+impl<'a, DI, RST, BL> LineBufferProvider for St7789LineBuffer<'a, DI, RST, BL>
+where
+    DI: display_interface::WriteOnlyDataCommand,
+    RST: OutputPin,
+    BL: OutputPin,
+{
+    type TargetPixel = Rgb565;
+
+    fn process_line(
+        &mut self,
+        line: usize,
+        range: Range<usize>,
+        render_fn: impl FnOnce(&mut [Self::TargetPixel]),
+    ) {
+        let pixels = &mut self.buffer[range.clone()];
+        render_fn(pixels);
+
+        let region = Rectangle::new(
+            Point::new(range.start as i32, line as i32),
+            Size::new(range.len() as u32, 1),
+        );
+        fill_region(self.display, region, pixels);
+    }
+}
+
+/// 用 Slint 驱动显示屏，取代 `embedded-graphics` 的直接 blit 路径。
+///
+/// 按键/编码器的防抖与合档不在这里重做一遍：调用方（`main.rs`）已经把
+/// 四个按键和编码器各自 spawn 成 `tasks::button_task`/`tasks::encoder_task`，
+/// 这两个任务跟 `embedded-graphics` 路径共用同一份中断驱动、已消抖的
+/// `tasks::EVENTS` 通道，这里只管把收到的 [`UiInput`] 转发成对应的菜单
+/// 回调。早先这里直接轮询 `keys[i].is_low()`/裸 QEI 差值，按键按住不放
+/// 会每个 tick 都重新触发一次回调，编码器也没有按 detent 合档——等于在
+/// 这条备用 UI 后端里把 `tasks` 模块刚解决的抖动/连发问题又带回来了。
+/// 触摸坐标照常转成指针事件，落在菜单项的 `TouchArea` 上才会触发点击。
+pub async fn run<DI, RST, BL>(mut display: ST7789<DI, RST, BL>, mut touch: Touch<'static>) -> !
+where
+    DI: display_interface::WriteOnlyDataCommand,
+    RST: OutputPin,
+    BL: OutputPin,
+{
+    let window = MinimalSoftwareWindow::new(RepaintBufferType::ReusedBuffer);
+    window.set_size(PhysicalSize::new(SCREEN_WIDTH as u32, SCREEN_HEIGHT as u32));
+    slint::platform::set_platform(Box::new(EmbassyPlatform {
+        window: window.clone(),
+        start: Instant::now(),
+    }))
+    .expect("Slint 平台只能设置一次");
+
+    let ui = AppWindow::new().expect("构造 Slint 组件失败");
+
+    // 触摸沿检测：跟 `main.rs` 主循环里的 `touch_down` 是同一个问题——
+    // `touch.read()` 只按压力判断"这一刻有没有压着"，手指按住不放会在
+    // 每个 tick 都重新采样到同一次触摸，不加这层判断会对着同一次按压
+    // 每 ~50ms 重发一轮 `PointerPressed`/`PointerReleased`，等效于按住
+    // 不放却被当成连续点击。
+    let mut touch_down = false;
+
+    loop {
+        slint::platform::update_timers_and_animations();
+
+        // 跟 `main.rs` 主循环同一个 `select`：优先处理 `tasks::EVENTS` 送来
+        // 的导航事件，否则在这个 tick 里顺带采样一次触摸。
+        match select(tasks::EVENTS.receive(), Timer::after_millis(KEY_SCAN_INTERVAL)).await {
+            Either::First(event) => match event {
+                // 按键在 `embedded-graphics` 路径上分别是 Select/Back/Next/
+                // Previous（见 `main.rs` 里 `tasks::button_task` 的绑定），
+                // 这里直接调用对应的菜单回调，而不是伪造一次屏幕中心的指针
+                // 点击——按键选中的是"当前菜单项"这个逻辑状态，不是屏幕上
+                // 某个固定坐标。
+                UiInput::Select => ui.invoke_activate(),
+                UiInput::Back => ui.invoke_back(),
+                UiInput::Next => ui.invoke_select_next(),
+                UiInput::Previous => ui.invoke_select_previous(),
+            },
+            Either::Second(()) => {
+                // 触摸坐标 -> 指针点击事件，落在菜单项/返回区域的
+                // `TouchArea` 上才会触发对应的 `clicked`/`back` 回调。
+                // 只在按下沿（上一帧没按、这一帧按了）派发一次点击；
+                // 按住不放时后续 tick 只是重新确认压力，不重复派发。
+                match touch.read().await {
+                    Some(point) if !touch_down => {
+                        touch_down = true;
+                        let position = LogicalPosition::new(point.x as f32, point.y as f32);
+                        window.dispatch_event(WindowEvent::PointerPressed {
+                            position,
+                            button: slint::platform::PointerEventButton::Left,
+                        });
+                        window.dispatch_event(WindowEvent::PointerReleased {
+                            position,
+                            button: slint::platform::PointerEventButton::Left,
+                        });
+                    }
+                    Some(_) => {}
+                    None => touch_down = false,
+                }
+            }
+        }
+
+        window.draw_if_needed(|renderer| {
+            let provider = St7789LineBuffer {
+                display: &mut display,
+                buffer: [Rgb565::BLACK; SCREEN_WIDTH as usize],
+            };
+            renderer.render_by_line(provider);
+        });
+    }
+}
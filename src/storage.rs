@@ -0,0 +1,111 @@
+//! 内部 Flash 上的简单 Wear-Leveling 键值存储（“EEPROM 模拟”）。
+//!
+//! STM32H723 没有独立的 EEPROM，这里把专用的最后一个 Flash 扇区当作一个
+//! `(tag, value)` 记录的追加日志：每次状态变化都在扇区末尾写入一条新记录
+//! 而不是原地改写，这样对 Flash 的磨损被均匀分摊到整个扇区；启动时从头
+//! 扫描，取每个 tag 最后出现的那条有效记录。扇区写满后整体擦除、只把
+//! 当前已知的最新状态重新写回开头（"compact"）。
+
+use embassy_stm32::flash::{Blocking, Flash};
+use embassy_stm32::peripherals::FLASH;
+use embedded_storage::nor_flash::{NorFlash, ReadNorFlash};
+
+/// 记录写入粒度（H7 Flash 的最小编程单位是 32 字节/256-bit）。
+const RECORD_SIZE: u32 = 32;
+/// 记录魔数，标记这是一条有效记录（0xFF 代表 Flash 擦除后的空白）。
+const RECORD_MAGIC: u8 = 0xA5;
+
+/// STM32H723 单 Bank 1 MiB Flash，扇区大小 128 KiB；用最后一个扇区做存储区。
+const SECTOR_SIZE: u32 = 128 * 1024;
+const FLASH_SIZE: u32 = 1024 * 1024;
+const STORAGE_OFFSET: u32 = FLASH_SIZE - SECTOR_SIZE;
+const STORAGE_SIZE: u32 = SECTOR_SIZE;
+
+/// 可以持久化保存的设置项。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tag {
+    /// 最后显示的图片索引。
+    CurrentImage = 0,
+    /// 背光开关状态（0/1）。
+    BacklightOn = 1,
+}
+
+/// 跨掉电持久化的键值存储，封装在专用 Flash 扇区上的追加日志。
+pub struct Storage {
+    flash: Flash<'static, Blocking>,
+    write_offset: u32,
+}
+
+impl Storage {
+    /// 扫描存储扇区，定位下一次追加写入的位置。
+    pub fn init(flash_peri: FLASH) -> Self {
+        let mut flash = Flash::new_blocking(flash_peri);
+        let mut write_offset = 0u32;
+        let mut buf = [0u8; RECORD_SIZE as usize];
+        while write_offset < STORAGE_SIZE {
+            let _ = flash.blocking_read(STORAGE_OFFSET + write_offset, &mut buf);
+            if buf[0] != RECORD_MAGIC {
+                break;
+            }
+            write_offset += RECORD_SIZE;
+        }
+        Self { flash, write_offset }
+    }
+
+    /// 返回某个 tag 当前生效的值（最后写入的那条记录），尚无记录时返回 `None`。
+    pub fn load(&mut self, tag: Tag) -> Option<u8> {
+        let mut buf = [0u8; RECORD_SIZE as usize];
+        let mut offset = 0u32;
+        let mut found = None;
+        while offset < self.write_offset {
+            let _ = self.flash.blocking_read(STORAGE_OFFSET + offset, &mut buf);
+            if buf[0] == RECORD_MAGIC && buf[1] == tag as u8 {
+                found = Some(buf[2]);
+            }
+            offset += RECORD_SIZE;
+        }
+        found
+    }
+
+    /// 追加写入一条新记录；扇区写满时先整体擦除再把当前已知状态重写一遍。
+    pub fn store(&mut self, tag: Tag, value: u8) {
+        if self.write_offset + RECORD_SIZE > STORAGE_SIZE {
+            self.compact(tag, value);
+            return;
+        }
+
+        let mut record = [0xFFu8; RECORD_SIZE as usize];
+        record[0] = RECORD_MAGIC;
+        record[1] = tag as u8;
+        record[2] = value;
+        if self
+            .flash
+            .blocking_write(STORAGE_OFFSET + self.write_offset, &record)
+            .is_ok()
+        {
+            self.write_offset += RECORD_SIZE;
+        }
+    }
+
+    /// 扇区已满：擦除整个扇区，把已知的最新状态（含刚刚这次变化）重新写到开头。
+    fn compact(&mut self, changed_tag: Tag, changed_value: u8) {
+        let mut current_image = self.load(Tag::CurrentImage);
+        let mut backlight_on = self.load(Tag::BacklightOn);
+        match changed_tag {
+            Tag::CurrentImage => current_image = Some(changed_value),
+            Tag::BacklightOn => backlight_on = Some(changed_value),
+        }
+
+        let _ = self
+            .flash
+            .blocking_erase(STORAGE_OFFSET, STORAGE_OFFSET + STORAGE_SIZE);
+        self.write_offset = 0;
+
+        if let Some(value) = current_image {
+            self.store(Tag::CurrentImage, value);
+        }
+        if let Some(value) = backlight_on {
+            self.store(Tag::BacklightOn, value);
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! 按键/编码器的中断驱动输入任务。
+//!
+//! 原来的主循环每 50ms 轮询一次按键电平，并用阻塞的 `while` 循环等按键
+//! 松开做消抖——按键按住不放时，连渲染都会被一起卡住。这里换成 EXTI 驱动
+//! 的 per-key 异步任务：每个任务 `await` 下降沿，再用一个定时器窗口确认
+//! 不是抖动，发一次按下事件后等抬起；编码器任务定期采样计数器，把原始
+//! 脉冲按每档的脉冲数（pulses-per-detent）合并成一次"前进/后退"。所有
+//! 任务把事件发到同一个 `embassy_sync` 通道，主循环只管消费事件、渲染
+//! 画面，永远不会被输入卡住，多个输入源也能并发处理。
+
+use embassy_stm32::exti::ExtiInput;
+use embassy_stm32::peripherals::TIM1;
+use embassy_stm32::timer::qei::Qei;
+use embassy_sync::blocking_mutex::raw::NoopRawMutex;
+use embassy_sync::channel::Channel;
+use embassy_time::Timer;
+
+use crate::ui::Input as UiInput;
+use crate::DEBOUNCE_DELAY;
+
+/// 编码器每转过一档物理刻度产生的原始脉冲数，需按具体型号标定。
+const PULSES_PER_DETENT: i32 = 4;
+/// 编码器采样间隔。
+const ENCODER_POLL_INTERVAL: u64 = 10;
+
+/// 所有输入任务共用的事件通道；容量 8 足够应付突发的多键/编码器事件。
+pub static EVENTS: Channel<NoopRawMutex, UiInput, 8> = Channel::new();
+
+/// 一个按键对应的防抖任务：等下降沿 -> 防抖窗口确认 -> 发一次事件 -> 等抬起。
+#[embassy_executor::task(pool_size = 4)]
+pub async fn button_task(mut pin: ExtiInput<'static>, event: UiInput) {
+    loop {
+        pin.wait_for_falling_edge().await;
+        Timer::after_millis(DEBOUNCE_DELAY).await;
+        if pin.is_low() {
+            EVENTS.send(event).await;
+            pin.wait_for_rising_edge().await;
+        }
+    }
+}
+
+/// 编码器任务：定期采样计数器，按 `PULSES_PER_DETENT` 把原始脉冲合并成一次
+/// "前进/后退"；采样不足一档的零头留到下一次继续累加，不丢失计数。
+#[embassy_executor::task]
+pub async fn encoder_task(mut qei: Qei<'static, TIM1>) {
+    let mut last = qei.count();
+    let mut accumulated = 0i32;
+    loop {
+        Timer::after_millis(ENCODER_POLL_INTERVAL).await;
+        let current = qei.count();
+        // `count()` 是个会在 0/65535 处回绕的硬件计数器，不能直接拿两次
+        // 采样做 `i32` 减法：一次回绕会被当成上万个脉冲的突变，撑爆下面
+        // 按 8 条余量设计的 `EVENTS` 通道。用 `wrapping_sub` 在原始宽度上
+        // 取差、再转回有符号数，拿到的才是这次轮询期间真正转过的量。
+        let delta = current.wrapping_sub(last) as i16 as i32;
+        accumulated += delta;
+        last = current;
+
+        while accumulated >= PULSES_PER_DETENT {
+            EVENTS.send(UiInput::Next).await;
+            accumulated -= PULSES_PER_DETENT;
+        }
+        while accumulated <= -PULSES_PER_DETENT {
+            EVENTS.send(UiInput::Previous).await;
+            accumulated += PULSES_PER_DETENT;
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! XPT2046/ADS7846 电阻式触摸屏驱动。
+//!
+//! 触摸控制器与显示屏共享 SPI1 总线（见 [`crate::bus`]），通过独立的
+//! `SpiDeviceWithConfig` 在每次收发前自动切到触摸所需的频率/模式。
+
+use embassy_time::{Duration, Timer};
+use embedded_graphics::prelude::Point;
+use embedded_hal::spi::SpiDevice;
+
+use crate::bus::SharedSpiDevice;
+
+/// 读取 X 通道的控制字节（启动位 + 通道选择 + 12 位模式 + 差分参考 + 省电模式）。
+const CMD_READ_X: u8 = 0xD0;
+/// 读取 Y 通道的控制字节。
+const CMD_READ_Y: u8 = 0x90;
+/// 读取压力 Z1 的控制字节。
+const CMD_READ_Z1: u8 = 0xB0;
+/// 读取压力 Z2 的控制字节。
+const CMD_READ_Z2: u8 = 0xC0;
+
+/// 每个坐标的采样次数，取中位数以抑制电阻屏的噪声。
+const SAMPLE_COUNT: usize = 5;
+/// 压力值低于该阈值时视为未触摸（数值越大代表压力越大）。
+const PRESSURE_MIN: i32 = 80;
+
+/// 出厂标定参数：把 12 位 ADC 原始值映射到屏幕坐标。
+/// 需要针对具体面板重新标定这四个数值。
+struct Calibration {
+    x_min: i32,
+    x_max: i32,
+    y_min: i32,
+    y_max: i32,
+}
+
+const CALIBRATION: Calibration = Calibration {
+    x_min: 200,
+    x_max: 3900,
+    y_min: 200,
+    y_max: 3900,
+};
+
+/// 电阻式触摸输入，返回校准后的屏幕坐标。
+pub struct Touch<'a> {
+    spi: SharedSpiDevice<'a>,
+}
+
+impl<'a> Touch<'a> {
+    pub fn new(spi: SharedSpiDevice<'a>) -> Self {
+        Self { spi }
+    }
+
+    /// 读取一次触摸事件。未检测到触摸（压力不足）时返回 `None`。
+    pub async fn read(&mut self) -> Option<Point> {
+        let z1 = self.read_channel(CMD_READ_Z1).ok()?;
+        let z2 = self.read_channel(CMD_READ_Z2).ok()?;
+        // z1/z2 的差值越大，代表压力越小，这里用一个简化近似判断触摸强度。
+        let pressure = 4095 - (z2 - z1);
+        if pressure < PRESSURE_MIN {
+            return None;
+        }
+
+        let x_raw = self.median_sample(CMD_READ_X).await.ok()?;
+        let y_raw = self.median_sample(CMD_READ_Y).await.ok()?;
+        Some(self.to_screen(x_raw, y_raw))
+    }
+
+    /// 对同一通道连续采样并取中位数，丢弃单次读数中的毛刺。
+    async fn median_sample(&mut self, cmd: u8) -> Result<i32, ()> {
+        let mut samples = [0i32; SAMPLE_COUNT];
+        for sample in samples.iter_mut() {
+            *sample = self.read_channel(cmd)?;
+            Timer::after(Duration::from_micros(50)).await;
+        }
+        samples.sort_unstable();
+        Ok(samples[SAMPLE_COUNT / 2])
+    }
+
+    /// 发送控制字节并读取 12 位转换结果（总线收发本身是阻塞的，不需要
+    /// `.await`；`read`/`median_sample` 仍是 `async fn` 只是为了上面采样
+    /// 间隔的 `Timer::after`）。
+    fn read_channel(&mut self, cmd: u8) -> Result<i32, ()> {
+        let mut rx = [0u8; 3];
+        let tx = [cmd, 0x00, 0x00];
+        self.spi.transfer(&mut rx, &tx).map_err(|_| ())?;
+        // 转换结果位于 rx[1..3]，右对齐为 12 位。
+        Ok((((rx[1] as i32) << 8) | rx[2] as i32) >> 3)
+    }
+
+    /// 把原始 ADC 值按标定参数映射到屏幕像素坐标，并裁剪到屏幕范围内。
+    fn to_screen(&self, x_raw: i32, y_raw: i32) -> Point {
+        let x = (x_raw - CALIBRATION.x_min) * crate::SCREEN_WIDTH
+            / (CALIBRATION.x_max - CALIBRATION.x_min);
+        let y = (y_raw - CALIBRATION.y_min) * crate::SCREEN_HEIGHT
+            / (CALIBRATION.y_max - CALIBRATION.y_min);
+        Point::new(
+            x.clamp(0, crate::SCREEN_WIDTH - 1),
+            y.clamp(0, crate::SCREEN_HEIGHT - 1),
+        )
+    }
+}
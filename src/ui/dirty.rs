@@ -0,0 +1,38 @@
+use embedded_graphics::primitives::Rectangle;
+
+/// 一帧内最多追踪的脏矩形数量，够用即可，超出的部分直接丢弃。
+const MAX_DIRTY_REGIONS: usize = 8;
+
+/// 每帧累积的"脏矩形"列表：各处局部刷新在画完之后把刷新过的区域记到
+/// 这里。列表只做记录，本身不会再向显示屏推送任何像素。
+pub struct DirtyList {
+    regions: [Option<Rectangle>; MAX_DIRTY_REGIONS],
+    len: usize,
+}
+
+impl DirtyList {
+    pub fn new() -> Self {
+        Self {
+            regions: [None; MAX_DIRTY_REGIONS],
+            len: 0,
+        }
+    }
+
+    /// 标记一块区域在本帧发生了变化。
+    pub fn mark(&mut self, region: Rectangle) {
+        if self.len < MAX_DIRTY_REGIONS {
+            self.regions[self.len] = Some(region);
+            self.len += 1;
+        }
+    }
+
+    /// 本帧累积的脏矩形。
+    pub fn regions(&self) -> impl Iterator<Item = &Rectangle> {
+        self.regions[..self.len].iter().filter_map(Option::as_ref)
+    }
+
+    /// 清空，为下一帧做准备。
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
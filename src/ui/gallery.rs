@@ -0,0 +1,64 @@
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_hal::digital::OutputPin;
+use st7789::ST7789;
+
+use super::{DirtyList, Input, Screen, Transition};
+
+/// 图库总张数（包括默认头像）。
+const TOTAL_IMAGES: u8 = crate::TOTAL_IMAGES;
+
+/// 图片画廊：用编码器/按键在几张内置图片间循环切换。
+pub struct ImageGalleryScreen {
+    current_image: u8,
+}
+
+impl ImageGalleryScreen {
+    /// 从持久化存储恢复的图库，直接定位到指定图片。
+    pub fn with_image(current_image: u8) -> Self {
+        Self { current_image }
+    }
+
+    /// 当前正在显示的图片索引，供调用方持久化保存。
+    pub fn current_image(&self) -> u8 {
+        self.current_image
+    }
+}
+
+impl Screen for ImageGalleryScreen {
+    fn draw(&mut self, display: &mut impl DrawTarget<Color = Rgb565>) {
+        crate::show_image_sync(display, self.current_image);
+    }
+
+    fn handle(&mut self, input: Input) -> Transition {
+        match input {
+            Input::Next => {
+                self.current_image = (self.current_image + 1) % TOTAL_IMAGES;
+                Transition::Stay
+            }
+            Input::Previous => {
+                self.current_image = (self.current_image + TOTAL_IMAGES - 1) % TOTAL_IMAGES;
+                Transition::Stay
+            }
+            Input::Select => Transition::Stay,
+            Input::Back => Transition::Pop,
+        }
+    }
+
+    fn draw_partial<DI, RST, BL>(&mut self, display: &mut ST7789<DI, RST, BL>, dirty: &mut DirtyList) -> bool
+    where
+        DI: display_interface::WriteOnlyDataCommand,
+        RST: OutputPin,
+        BL: OutputPin,
+    {
+        // 翻页换的是整张 240x240 的图，没有比"整屏"更小的脏区域可言；
+        // 还是走同一条 `show_image_sync` 路径，只是把这次改动按
+        // `DirtyList` 的记账方式记下来，跟 HUD 数字的局部刷新共用同一套
+        // 账本，而不是游离在外。
+        self.draw(display);
+        dirty.mark(Rectangle::new(Point::zero(), Size::new(240, 240)));
+        true
+    }
+}
@@ -0,0 +1,138 @@
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_hal::digital::OutputPin;
+use st7789::ST7789;
+
+use crate::widgets::{draw_button, draw_button_partial};
+
+use super::{DirtyList, ImageGalleryScreen, Input, Screen, Screens, SettingsScreen, Transition};
+
+/// 主屏幕上可以进入的子菜单项。
+const MENU_ITEMS: [&str; 2] = ["图片画廊", "设置"];
+/// 每个菜单按钮的位置与尺寸（触摸热区与绘制区域共用同一份布局）。
+const BUTTON_RECTS: [Rectangle; 2] = [
+    Rectangle::new(Point::new(40, 80), Size::new(160, 36)),
+    Rectangle::new(Point::new(40, 130), Size::new(160, 36)),
+];
+/// 选中项的高亮填充色（浮雕面板常见的浅灰，选中时稍亮一些）。
+const SELECTED_FILL: Rgb565 = Rgb565::new(25, 50, 25);
+/// 未选中项的普通填充色（浮雕面板常见的浅灰）。
+const NORMAL_FILL: Rgb565 = Rgb565::new(22, 44, 22);
+
+/// 起始屏幕：显示默认头像，并用编码器/按键/触摸在几个子菜单按钮间选择。
+pub struct HomeScreen {
+    selected: usize,
+    /// 最近一次已知的背光状态，进入设置页时带入，避免显示跟硬件不一致。
+    backlight_on: bool,
+    /// 最近一次已知的图库所在图片，重新进入图库时带入，避免从图片 1
+    /// 重新开始浏览，丢掉用户（或者掉电恢复）已经翻到的位置。
+    current_image: u8,
+    /// 上一次整屏/局部绘制时已经画出来的选中项，`None` 表示还没画过一次；`draw_partial` 拿它跟 `selected` 比较，
+    /// 判断这次只需要重画哪两个按钮，还是得退回整屏重绘。
+    last_drawn: Option<usize>,
+}
+
+impl HomeScreen {
+    /// 带入当前（或从持久化存储恢复的）背光状态与图库位置，进入对应子
+    /// 屏幕时会原样带过去。
+    pub fn new(backlight_on: bool, current_image: u8) -> Self {
+        Self {
+            selected: 0,
+            backlight_on,
+            current_image,
+            last_drawn: None,
+        }
+    }
+
+    /// 同步设置页离开后（用户切换过背光）的最新状态，供下次进入设置页使用。
+    pub fn set_backlight(&mut self, backlight_on: bool) {
+        self.backlight_on = backlight_on;
+    }
+
+    /// 同步图库离开后（用户翻过页）的最新图片，供下次进入图库使用。
+    pub fn set_current_image(&mut self, current_image: u8) {
+        self.current_image = current_image;
+    }
+
+    fn push_for(&self, selected: usize) -> Transition {
+        match selected {
+            0 => Transition::Push(Screens::ImageGallery(ImageGalleryScreen::with_image(self.current_image))),
+            _ => Transition::Push(Screens::Settings(SettingsScreen::new(self.backlight_on))),
+        }
+    }
+}
+
+impl Screen for HomeScreen {
+    fn draw(&mut self, display: &mut impl DrawTarget<Color = Rgb565>) {
+        crate::show_image_sync(display, 0);
+
+        for (idx, rect) in BUTTON_RECTS.iter().enumerate() {
+            let fill = if idx == self.selected {
+                SELECTED_FILL
+            } else {
+                NORMAL_FILL
+            };
+            draw_button(display, *rect, MENU_ITEMS[idx], fill);
+        }
+        self.last_drawn = Some(self.selected);
+    }
+
+    fn handle(&mut self, input: Input) -> Transition {
+        match input {
+            Input::Next => {
+                self.selected = (self.selected + 1) % MENU_ITEMS.len();
+                Transition::Stay
+            }
+            Input::Previous => {
+                self.selected = (self.selected + MENU_ITEMS.len() - 1) % MENU_ITEMS.len();
+                Transition::Stay
+            }
+            Input::Select => self.push_for(self.selected),
+            Input::Back => Transition::Stay,
+        }
+    }
+
+    fn hit_test(&mut self, point: Point) -> Option<Input> {
+        for (idx, rect) in BUTTON_RECTS.iter().enumerate() {
+            if rect.contains(point) {
+                self.selected = idx;
+                return Some(Input::Select);
+            }
+        }
+        None
+    }
+
+    fn draw_partial<DI, RST, BL>(&mut self, display: &mut ST7789<DI, RST, BL>, dirty: &mut DirtyList) -> bool
+    where
+        DI: display_interface::WriteOnlyDataCommand,
+        RST: OutputPin,
+        BL: OutputPin,
+    {
+        match self.last_drawn {
+            // 选中项变了：头像没变，只需要把旧选中项退成普通样式、新选中
+            // 项改成高亮样式，不用碰其它像素。
+            Some(previous) if previous != self.selected => {
+                draw_button_partial(display, BUTTON_RECTS[previous], MENU_ITEMS[previous], NORMAL_FILL, dirty);
+                draw_button_partial(
+                    display,
+                    BUTTON_RECTS[self.selected],
+                    MENU_ITEMS[self.selected],
+                    SELECTED_FILL,
+                    dirty,
+                );
+                self.last_drawn = Some(self.selected);
+                false
+            }
+            // 选中项没变（比如按了 Back，Home 自己原地不动）：什么都不用画。
+            Some(_) => false,
+            // 还没画过一次：没有旧状态可以比较，退回
+            // 整屏重绘，顺带把头像也画出来。
+            None => {
+                self.draw(display);
+                true
+            }
+        }
+    }
+}
@@ -0,0 +1,12 @@
+/// 导航事件，由编码器旋转或按键触发，与具体的引脚/编码器增量无关。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    /// 编码器正向旋转，或下一项按键。
+    Next,
+    /// 编码器反向旋转，或上一项按键。
+    Previous,
+    /// 确认/进入。
+    Select,
+    /// 返回上一级屏幕。
+    Back,
+}
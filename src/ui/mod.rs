@@ -0,0 +1,22 @@
+//! 事件驱动的屏幕/菜单框架。
+//!
+//! 用一个 [`Screen`] trait 取代原来扁平的 `current_image` 计数器和主循环里
+//! 四段复制粘贴的按键处理：每个屏幕自己负责绘制、自己决定如何响应
+//! [`Input`] 事件，并可以压入/弹出子屏幕，从而把"图片切换器"变成一个
+//! 可以继续长出菜单、设置页的通用 UI 框架。
+
+mod dirty;
+mod gallery;
+mod home;
+mod input;
+mod screen;
+mod settings;
+mod stack;
+
+pub use dirty::DirtyList;
+pub use gallery::ImageGalleryScreen;
+pub use home::HomeScreen;
+pub use input::Input;
+pub use screen::{Screen, Screens, Transition};
+pub use settings::SettingsScreen;
+pub use stack::ScreenStack;
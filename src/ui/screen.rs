@@ -0,0 +1,99 @@
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::Point;
+use embedded_hal::digital::OutputPin;
+use st7789::ST7789;
+
+use super::{DirtyList, HomeScreen, ImageGalleryScreen, Input, SettingsScreen};
+
+/// 屏幕处理一次输入事件后返回的导航指令。
+pub enum Transition {
+    /// 停留在当前屏幕。
+    Stay,
+    /// 压入一个新的子屏幕。
+    Push(Screens),
+    /// 弹出当前屏幕，回到上一级。
+    Pop,
+}
+
+/// 所有具体屏幕共用的行为：绘制自身、响应输入并决定是否切换屏幕。
+pub trait Screen {
+    /// 将屏幕内容绘制到显示设备上。
+    fn draw(&mut self, display: &mut impl DrawTarget<Color = Rgb565>);
+    /// 处理一次输入事件，返回导航指令。
+    fn handle(&mut self, input: Input) -> Transition;
+
+    /// 把一次触摸坐标翻译成输入事件，默认不识别任何触摸热区。
+    /// 只有画了可点击控件（比如 [`crate::widgets::draw_button`]）的屏幕
+    /// 才需要覆盖这个方法；命中一个按钮时可以顺带更新自身状态（比如把
+    /// 被点中的项设为当前选中项），再返回等效的导航事件。
+    fn hit_test(&mut self, _point: Point) -> Option<Input> {
+        None
+    }
+
+    /// 增量重绘：屏幕如果能判断这次只需要局部更新（比如切换按钮高亮），
+    /// 就只经驱动的地址窗口刷新变化的区域并记到 `dirty` 里，不必每次都走
+    /// [`Screen::draw`] 推整屏像素。局部刷新要用到驱动自己的地址窗口接口，所以只能接受具体的
+    /// `ST7789<DI, RST, BL>`，不能像 `draw` 那样泛化成任意 `DrawTarget`。
+    ///
+    /// 返回 `true` 表示这一次确实整屏重绘了（调用方要据此强制下一帧重画
+    /// HUD，见 `main.rs` 里 `last_hud_*` 的复位），`false` 表示只做了局部
+    /// 更新、屏幕其余部分（包括 HUD 叠加层）没有被触碰。默认实现没有
+    /// 局部更新可做，直接退化为整屏 `draw`。
+    fn draw_partial<DI, RST, BL>(&mut self, display: &mut ST7789<DI, RST, BL>, dirty: &mut DirtyList) -> bool
+    where
+        DI: display_interface::WriteOnlyDataCommand,
+        RST: OutputPin,
+        BL: OutputPin,
+    {
+        let _ = dirty;
+        self.draw(display);
+        true
+    }
+}
+
+/// 应用中全部具体屏幕的集合，用枚举分发代替 trait object（no_std 无堆分配）。
+pub enum Screens {
+    Home(HomeScreen),
+    ImageGallery(ImageGalleryScreen),
+    Settings(SettingsScreen),
+}
+
+impl Screen for Screens {
+    fn draw(&mut self, display: &mut impl DrawTarget<Color = Rgb565>) {
+        match self {
+            Screens::Home(screen) => screen.draw(display),
+            Screens::ImageGallery(screen) => screen.draw(display),
+            Screens::Settings(screen) => screen.draw(display),
+        }
+    }
+
+    fn handle(&mut self, input: Input) -> Transition {
+        match self {
+            Screens::Home(screen) => screen.handle(input),
+            Screens::ImageGallery(screen) => screen.handle(input),
+            Screens::Settings(screen) => screen.handle(input),
+        }
+    }
+
+    fn hit_test(&mut self, point: Point) -> Option<Input> {
+        match self {
+            Screens::Home(screen) => screen.hit_test(point),
+            Screens::ImageGallery(screen) => screen.hit_test(point),
+            Screens::Settings(screen) => screen.hit_test(point),
+        }
+    }
+
+    fn draw_partial<DI, RST, BL>(&mut self, display: &mut ST7789<DI, RST, BL>, dirty: &mut DirtyList) -> bool
+    where
+        DI: display_interface::WriteOnlyDataCommand,
+        RST: OutputPin,
+        BL: OutputPin,
+    {
+        match self {
+            Screens::Home(screen) => screen.draw_partial(display, dirty),
+            Screens::ImageGallery(screen) => screen.draw_partial(display, dirty),
+            Screens::Settings(screen) => screen.draw_partial(display, dirty),
+        }
+    }
+}
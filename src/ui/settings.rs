@@ -0,0 +1,50 @@
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::mono_font::{ascii::FONT_6X13_ITALIC, MonoTextStyle};
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+use embedded_graphics::text::Text;
+
+use super::{Input, Screen, Transition};
+
+/// 设置页：目前只暴露背光开关，后续菜单项可以继续往这里加。
+pub struct SettingsScreen {
+    backlight_on: bool,
+}
+
+impl SettingsScreen {
+    /// 带入当前（或从持久化存储恢复的）背光状态，保证显示跟硬件一致。
+    pub fn new(backlight_on: bool) -> Self {
+        Self { backlight_on }
+    }
+
+    /// 当前的背光开关状态，供调用方持久化保存/驱动硬件。
+    pub fn backlight_on(&self) -> bool {
+        self.backlight_on
+    }
+}
+
+impl Screen for SettingsScreen {
+    fn draw(&mut self, display: &mut impl DrawTarget<Color = Rgb565>) {
+        let _ = Rectangle::new(Point::new(0, 0), Size::new(240, 240))
+            .into_styled(PrimitiveStyle::with_fill(Rgb565::BLACK))
+            .draw(display);
+
+        let style = MonoTextStyle::new(&FONT_6X13_ITALIC, Rgb565::WHITE);
+        let _ = Text::new("设置", Point::new(8, 16), style).draw(display);
+
+        let state = if self.backlight_on { "背光: 开" } else { "背光: 关" };
+        let _ = Text::new(state, Point::new(8, 32), style).draw(display);
+    }
+
+    fn handle(&mut self, input: Input) -> Transition {
+        match input {
+            Input::Select => {
+                self.backlight_on = !self.backlight_on;
+                Transition::Stay
+            }
+            Input::Back => Transition::Pop,
+            Input::Next | Input::Previous => Transition::Stay,
+        }
+    }
+}
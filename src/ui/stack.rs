@@ -0,0 +1,83 @@
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_hal::digital::OutputPin;
+use st7789::ST7789;
+
+use super::{DirtyList, Input, Screen, Screens, Transition};
+
+/// 屏幕导航栈支持的最大深度（root + 子屏幕），定长数组避免堆分配。
+const MAX_DEPTH: usize = 4;
+
+/// 屏幕导航栈：驱动 [`Screens`] 的 push/pop，持有当前激活的屏幕链。
+pub struct ScreenStack {
+    stack: [Option<Screens>; MAX_DEPTH],
+    len: usize,
+}
+
+impl ScreenStack {
+    /// 以给定的根屏幕创建导航栈。
+    pub fn new(root: Screens) -> Self {
+        let mut stack = [None, None, None, None];
+        stack[0] = Some(root);
+        Self { stack, len: 1 }
+    }
+
+    /// 直接压入一个子屏幕，绕过 [`Transition`]；仅用于启动时恢复导航状态。
+    pub fn push(&mut self, screen: Screens) {
+        if self.len < MAX_DEPTH {
+            self.stack[self.len] = Some(screen);
+            self.len += 1;
+        }
+    }
+
+    /// 当前激活（栈顶）的屏幕。
+    pub fn current(&mut self) -> &mut Screens {
+        self.stack[self.len - 1]
+            .as_mut()
+            .expect("导航栈不允许为空")
+    }
+
+    /// 把一次输入事件派发给当前屏幕，并按返回的导航指令更新栈。返回这次
+    /// 派发是否换到了另一个屏幕（`Push`/`Pop`）——调用方据此决定要不要退回
+    /// 整屏 [`ScreenStack::draw`]：压入/弹出后激活的是另一个屏幕实例（或者
+    /// 带着上次在别处时的旧画面弹回来的老实例），没法用
+    /// [`ScreenStack::draw_partial`] 里"跟上次画的状态比对"那套逻辑来局部
+    /// 刷新，必须整屏重绘一次。
+    pub fn dispatch(&mut self, input: Input) -> bool {
+        match self.current().handle(input) {
+            Transition::Stay => false,
+            Transition::Push(screen) => {
+                if self.len < MAX_DEPTH {
+                    self.stack[self.len] = Some(screen);
+                    self.len += 1;
+                }
+                true
+            }
+            Transition::Pop => {
+                if self.len > 1 {
+                    self.stack[self.len - 1] = None;
+                    self.len -= 1;
+                }
+                true
+            }
+        }
+    }
+
+    /// 绘制当前激活的屏幕。
+    pub fn draw(&mut self, display: &mut impl DrawTarget<Color = Rgb565>) {
+        self.current().draw(display);
+    }
+
+    /// 增量重绘当前激活的屏幕，只在屏幕本身没有换过（见 [`ScreenStack::dispatch`]
+    /// 的返回值）时调用；具体局部刷新与否由 [`Screen::draw_partial`] 决定。
+    /// 返回值同 `draw_partial`：`true` 表示这次其实整屏重绘了，调用方要
+    /// 强制下一帧重画 HUD。
+    pub fn draw_partial<DI, RST, BL>(&mut self, display: &mut ST7789<DI, RST, BL>, dirty: &mut DirtyList) -> bool
+    where
+        DI: display_interface::WriteOnlyDataCommand,
+        RST: OutputPin,
+        BL: OutputPin,
+    {
+        self.current().draw_partial(display, dirty)
+    }
+}
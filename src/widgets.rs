@@ -0,0 +1,101 @@
+//! 3D 浮雕风格的按钮控件。
+//!
+//! 经典的凸起面板观感：浅灰色填充，左上角一圈高光、右下角一圈阴影，向内
+//! 收一像素再描一圈做出双层浮雕厚度，标签用等宽字体居中；按钮太窄放不
+//! 下一整行字形时直接跳过文字，只留浮雕外观。
+
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::mono_font::{ascii::FONT_6X13, MonoTextStyle};
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{Line, PrimitiveStyle, Rectangle};
+use embedded_graphics::text::{Alignment, Baseline, Text, TextStyleBuilder};
+use embedded_hal::digital::OutputPin;
+use st7789::ST7789;
+
+use crate::ui::DirtyList;
+
+/// 浮雕高光色：按钮左侧/顶部的外描边。
+const HIGHLIGHT: Rgb565 = Rgb565::new(31, 63, 31);
+/// 浮雕阴影色：按钮右侧/底部的外描边。
+const SHADOW: Rgb565 = Rgb565::new(6, 12, 6);
+/// 标签文字颜色。
+const LABEL_COLOR: Rgb565 = Rgb565::BLACK;
+
+/// 绘制一个带 3D 浮雕效果的按钮：`fill` 填充主体，描出立体边框，并把
+/// `label` 居中写在按钮上。
+pub fn draw_button(display: &mut impl DrawTarget<Color = Rgb565>, rect: Rectangle, label: &str, fill: Rgb565) {
+    let _ = rect
+        .into_styled(PrimitiveStyle::with_fill(fill))
+        .draw(display);
+
+    let top_left = rect.top_left;
+    let bottom_right = Point::new(
+        top_left.x + rect.size.width as i32 - 1,
+        top_left.y + rect.size.height as i32 - 1,
+    );
+
+    // 外层浮雕。
+    draw_bevel(display, top_left, bottom_right);
+    // 内层浮雕，向内收 1 像素，让边框有双层厚度的质感。
+    draw_bevel(
+        display,
+        Point::new(top_left.x + 1, top_left.y + 1),
+        Point::new(bottom_right.x - 1, bottom_right.y - 1),
+    );
+
+    let glyph = FONT_6X13.character_size;
+    let text_width = glyph.width as i32 * label.chars().count() as i32;
+    if text_width > rect.size.width as i32 || glyph.height as i32 > rect.size.height as i32 {
+        // 按钮太小放不下一整行字形，跳过文字只留浮雕外观。
+        return;
+    }
+
+    let style = MonoTextStyle::new(&FONT_6X13, LABEL_COLOR);
+    let text_style = TextStyleBuilder::new()
+        .alignment(Alignment::Center)
+        .baseline(Baseline::Middle)
+        .build();
+    let center = Point::new(
+        top_left.x + rect.size.width as i32 / 2,
+        top_left.y + rect.size.height as i32 / 2,
+    );
+    let _ = Text::with_text_style(label, center, style, text_style).draw(display);
+}
+
+/// 和 [`draw_button`] 画的是同一个按钮，但只刷新这一块矩形区域（ST7789
+/// 自己的 `DrawTarget` 实现已经把按钮的每一笔图元都收窄到各自的地址
+/// 窗口，不会碰到屏幕其它部分），并把这块区域记到 `dirty` 里（本帧是否
+/// 只做了局部更新由 [`Screen::draw_partial`](crate::ui::Screen::draw_partial)
+/// 的返回值表示）。
+/// 用来给"切换按钮高亮"这种只改了一小块屏幕的交互省掉整屏重绘的开销。
+pub fn draw_button_partial<DI, RST, BL>(
+    display: &mut ST7789<DI, RST, BL>,
+    rect: Rectangle,
+    label: &str,
+    fill: Rgb565,
+    dirty: &mut DirtyList,
+) where
+    DI: display_interface::WriteOnlyDataCommand,
+    RST: OutputPin,
+    BL: OutputPin,
+{
+    draw_button(display, rect, label, fill);
+    dirty.mark(rect);
+}
+
+/// 画一圈浮雕边框：左、上两条线用高光色，右、下两条线用阴影色。
+fn draw_bevel(display: &mut impl DrawTarget<Color = Rgb565>, top_left: Point, bottom_right: Point) {
+    let light = PrimitiveStyle::with_stroke(HIGHLIGHT, 1);
+    let dark = PrimitiveStyle::with_stroke(SHADOW, 1);
+
+    let top = Line::new(top_left, Point::new(bottom_right.x, top_left.y));
+    let left = Line::new(top_left, Point::new(top_left.x, bottom_right.y));
+    let bottom = Line::new(Point::new(top_left.x, bottom_right.y), bottom_right);
+    let right = Line::new(Point::new(bottom_right.x, top_left.y), bottom_right);
+
+    let _ = top.into_styled(light).draw(display);
+    let _ = left.into_styled(light).draw(display);
+    let _ = bottom.into_styled(dark).draw(display);
+    let _ = right.into_styled(dark).draw(display);
+}